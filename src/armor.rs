@@ -0,0 +1,193 @@
+//! ASCII-armor encoding, modeled on the RFC 4880 (OpenPGP) armor format.
+//!
+//! Wraps a byte stream in a self-describing, checksummed envelope so it can be pasted into
+//! text-only contexts (emails, issues, chat) and round-tripped back to the original bytes. This
+//! is layered on top of the same standard base64 alphabet the CLI's `--base64` output uses, just
+//! with headers, line wrapping, and a CRC-24 checksum added around it.
+
+use std::io::{self, Read, Write};
+
+const BEGIN_LINE: &str = "-----BEGIN XORCISM MESSAGE-----";
+const END_LINE: &str = "-----END XORCISM MESSAGE-----";
+const LINE_WIDTH: usize = 64;
+
+const CRC24_INIT: u32 = 0x00B7_04CE;
+const CRC24_POLY: u32 = 0x0186_4CFB;
+const CRC24_MASK: u32 = 0x00FF_FFFF;
+
+/// Compute the OpenPGP CRC-24 checksum of `data`.
+fn crc24(data: &[u8]) -> u32 {
+    let mut crc = CRC24_INIT;
+    for &b in data {
+        crc ^= (b as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x0100_0000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+    crc & CRC24_MASK
+}
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Wraps an underlying writer, ASCII-armoring everything written to it.
+///
+/// Input is buffered in memory, since the checksum and headers can only be written once the
+/// whole message is known. The envelope is written to the underlying writer by [`Writer::finish`];
+/// dropping the `Writer` without calling `finish` writes it anyway, best-effort, discarding any
+/// I/O error (mirroring how other wrapping writers in the ecosystem, e.g. `flate2`'s encoders,
+/// finish themselves on drop).
+pub struct Writer<W>
+where
+    W: Write,
+{
+    inner: Option<W>,
+    buffer: Vec<u8>,
+}
+
+impl<W> Writer<W>
+where
+    W: Write,
+{
+    /// Create a new armor `Writer` wrapping `inner`.
+    pub fn new(inner: W) -> Writer<W> {
+        Writer {
+            inner: Some(inner),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Write the complete armor envelope to the underlying writer and return it.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.finish_impl()
+    }
+
+    fn finish_impl(&mut self) -> io::Result<W> {
+        let mut inner = self.inner.take().expect("finish called more than once");
+
+        let checksum = crc24(&self.buffer).to_be_bytes();
+        let checksum = base64::encode(&checksum[1..]);
+        let body = base64::encode(&self.buffer);
+
+        writeln!(inner, "{}", BEGIN_LINE)?;
+        writeln!(inner)?;
+        for line in body.as_bytes().chunks(LINE_WIDTH) {
+            inner.write_all(line)?;
+            writeln!(inner)?;
+        }
+        writeln!(inner, "={}", checksum)?;
+        writeln!(inner, "{}", END_LINE)?;
+
+        Ok(inner)
+    }
+}
+
+impl<W> Write for Writer<W>
+where
+    W: Write,
+{
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(data);
+        Ok(data.len())
+    }
+
+    /// This is a no-op: nothing is written to the underlying writer until [`Writer::finish`].
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<W> Drop for Writer<W>
+where
+    W: Write,
+{
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.finish_impl();
+        }
+    }
+}
+
+/// Wraps an underlying reader, stripping an ASCII-armor envelope and verifying its checksum.
+///
+/// The envelope must be fully present before any bytes can be decoded, so the first call to
+/// [`Read::read`] consumes `reader` to EOF, validates and decodes it, and serves the result from
+/// an in-memory buffer on this and subsequent calls.
+pub struct Reader<R> {
+    reader: R,
+    decoded: Option<io::Cursor<Vec<u8>>>,
+}
+
+impl<R> Reader<R>
+where
+    R: Read,
+{
+    /// Create a new armor `Reader` wrapping `reader`.
+    pub fn new(reader: R) -> Reader<R> {
+        Reader {
+            reader,
+            decoded: None,
+        }
+    }
+
+    fn decoded(&mut self) -> io::Result<&mut io::Cursor<Vec<u8>>> {
+        if self.decoded.is_none() {
+            let mut armored = String::new();
+            self.reader.read_to_string(&mut armored)?;
+            self.decoded = Some(io::Cursor::new(decode(&armored)?));
+        }
+        Ok(self.decoded.as_mut().expect("just initialized"))
+    }
+}
+
+impl<R> Read for Reader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.decoded()?.read(buf)
+    }
+}
+
+/// Strip the headers/whitespace from an armored message, verify its checksum, and decode it.
+fn decode(armored: &str) -> io::Result<Vec<u8>> {
+    let mut lines = armored.lines().map(str::trim);
+
+    if lines.next() != Some(BEGIN_LINE) {
+        return Err(invalid_data("missing armor begin line"));
+    }
+
+    let mut body = String::new();
+    let mut checksum = None;
+    for line in lines {
+        if line.is_empty() {
+            continue;
+        }
+        if line == END_LINE {
+            break;
+        }
+        if let Some(stripped) = line.strip_prefix('=') {
+            checksum = Some(stripped);
+        } else {
+            body.push_str(line);
+        }
+    }
+
+    let checksum = checksum.ok_or_else(|| invalid_data("missing armor checksum line"))?;
+    let checksum = base64::decode(checksum).map_err(|e| invalid_data(e.to_string()))?;
+    if checksum.len() != 3 {
+        return Err(invalid_data("armor checksum must be 3 bytes"));
+    }
+    let checksum = u32::from_be_bytes([0, checksum[0], checksum[1], checksum[2]]);
+
+    let data = base64::decode(&body).map_err(|e| invalid_data(e.to_string()))?;
+    if crc24(&data) != checksum {
+        return Err(invalid_data("armor checksum mismatch"));
+    }
+
+    Ok(data)
+}
@@ -0,0 +1,117 @@
+//! Crate-local stand-ins for `std::io::{Read, Write}`, so the munging types can be named and
+//! implemented without depending on `std`.
+//!
+//! With the `std` feature enabled (the default), [`Read`] and [`Write`] are blanket-implemented
+//! for every type implementing the corresponding `std::io` trait, so code written against
+//! `std::io::{Read, Write}` — `File`, `TcpStream`, `&[u8]`, ... — satisfies these traits for
+//! free. With `std` disabled, callers implement [`Read`]/[`Write`] directly for their own
+//! embedded-friendly types.
+
+use core::fmt;
+
+/// A minimal, `no_std`-friendly stand-in for [`std::io::Error`].
+///
+/// Under the `std` feature, this carries the [`std::io::ErrorKind`] of the error it was
+/// converted from, so round-tripping an I/O error through this type (as `Writer`/`Reader` do)
+/// doesn't erase what kind of failure it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(not(feature = "std"), derive(Default))]
+pub struct Error {
+    #[cfg(feature = "std")]
+    kind: std::io::ErrorKind,
+}
+
+#[cfg(feature = "std")]
+impl Default for Error {
+    fn default() -> Error {
+        Error {
+            kind: std::io::ErrorKind::Other,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        #[cfg(feature = "std")]
+        {
+            write!(f, "xorcism I/O error: {}", self.kind)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            f.write_str("xorcism I/O error")
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Error {
+        Error { kind: e.kind() }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(e: Error) -> std::io::Error {
+        std::io::Error::new(e.kind, "xorcism I/O error")
+    }
+}
+
+/// Mirrors [`std::io::Read`].
+pub trait Read {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error>;
+}
+
+/// Mirrors [`std::io::Write`].
+pub trait Write {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error>;
+
+    fn flush(&mut self) -> Result<(), Error>;
+}
+
+/// Write the whole of `buf` to `writer`, retrying on short writes.
+///
+/// Not part of the [`Write`] trait itself (which mirrors `std::io::Write`'s required methods
+/// only), to keep that trait a minimal, unambiguous mirror for `no_std` implementers.
+pub(crate) fn write_all<W: Write + ?Sized>(writer: &mut W, mut buf: &[u8]) -> Result<(), Error> {
+    while !buf.is_empty() {
+        match writer.write(buf)? {
+            0 => return Err(write_zero_error()),
+            n => buf = &buf[n..],
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "std")]
+fn write_zero_error() -> Error {
+    Error {
+        kind: std::io::ErrorKind::WriteZero,
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn write_zero_error() -> Error {
+    Error {}
+}
+
+#[cfg(feature = "std")]
+impl<T: std::io::Read + ?Sized> Read for T {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        std::io::Read::read(self, buf).map_err(Into::into)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: std::io::Write + ?Sized> Write for T {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        std::io::Write::write(self, buf).map_err(Into::into)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        std::io::Write::flush(self).map_err(Into::into)
+    }
+}
@@ -1,5 +1,19 @@
-use std::borrow::Borrow;
-use std::io::{self, Read, Write};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+extern crate alloc;
+
+use core::borrow::Borrow;
+
+mod io;
+pub use io::{Error, Read, Write};
+use io::write_all;
+
+#[cfg(feature = "std")]
+pub mod armor;
 
 /// A munger which XORs a key with some data
 ///
@@ -27,6 +41,37 @@ impl<'a> Xorcism<'a> {
         Xorcism { key, pos: 0 }
     }
 
+    /// Create a new Xorcism munger from a key, starting at `pos` in the keystream.
+    ///
+    /// Equivalent to `Xorcism::new(key)` followed by [`set_position`](Self::set_position).
+    pub fn with_position<Key>(key: &'a Key, pos: usize) -> Xorcism<'a>
+    where
+        Key: AsRef<[u8]> + ?Sized,
+    {
+        let mut xorcism = Xorcism::new(key);
+        xorcism.set_position(pos);
+        xorcism
+    }
+
+    /// Get the current position in the keystream.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Set the current position in the keystream.
+    ///
+    /// Since the key simply cycles, positions are equivalent modulo the key length: this stores
+    /// `pos % key.len()`, so the cursor never grows unboundedly across repeated seeks. An empty
+    /// key has no positions to cycle through, so it's left at `0`, matching the no-op behavior of
+    /// [`munge_in_place`](Self::munge_in_place) on an empty key.
+    pub fn set_position(&mut self, pos: usize) {
+        self.pos = if self.key.is_empty() {
+            0
+        } else {
+            pos % self.key.len()
+        };
+    }
+
     /// Increase the stored pos by the specified amount, returning the old value.
     fn incr_pos(&mut self, by: usize) -> usize {
         let old_pos = self.pos;
@@ -77,6 +122,7 @@ impl<'a> Xorcism<'a> {
         Writer {
             xorcism: self,
             writer,
+            buf: [0u8; MAX_BUF_SIZE],
         }
     }
 
@@ -92,9 +138,15 @@ impl<'a> Xorcism<'a> {
     }
 }
 
+#[cfg(feature = "std")]
+use std::vec::Vec;
+#[cfg(all(not(feature = "std"), feature = "alloc"))]
+use alloc::vec::Vec;
+
 /// XOR each byte of `key` with each byte of `data`, looping `key` as required.
 ///
 /// This is stateless: repeated calls with identical inputs will always produce identical results.
+#[cfg(any(feature = "std", feature = "alloc"))]
 pub fn munge<Key, Data>(key: Key, data: Data) -> Vec<u8>
 where
     Key: AsRef<[u8]>,
@@ -107,11 +159,19 @@ where
     xorcism.munge(data).collect()
 }
 
+/// Size of the buffer [`Writer`] munges through on each write.
+///
+/// Input larger than this is processed in successive chunks, so `Writer`'s memory use is
+/// constant regardless of payload size. The buffer lives in the `Writer` itself rather than on
+/// `write`'s stack frame, so it's allocated once, at construction, instead of on every call.
+pub const MAX_BUF_SIZE: usize = 64 * 1024;
+
 /// This implements `Write` and performs xor munging on the data stream.
 #[derive(Clone)]
 pub struct Writer<'a, W> {
     xorcism: Xorcism<'a>,
     writer: W,
+    buf: [u8; MAX_BUF_SIZE],
 }
 
 impl<'a, W> Writer<'a, W>
@@ -125,24 +185,55 @@ where
         Writer {
             xorcism: Xorcism::new(key),
             writer,
+            buf: [0u8; MAX_BUF_SIZE],
         }
     }
+
+    /// This implementation will block until the underlying writer
+    /// has written the entire input buffer.
+    fn write_impl(&mut self, data: &[u8]) -> Result<usize, Error> {
+        for chunk in data.chunks(MAX_BUF_SIZE) {
+            let chunk_buf = &mut self.buf[..chunk.len()];
+            chunk_buf.copy_from_slice(chunk);
+            self.xorcism.munge_in_place(chunk_buf);
+            write_all(&mut self.writer, chunk_buf)?;
+        }
+        Ok(data.len())
+    }
+
+    fn flush_impl(&mut self) -> Result<(), Error> {
+        self.writer.flush()
+    }
 }
 
+// Under the `std` feature, `Writer` implements `std::io::Write` directly (below), and the
+// blanket impl in `io` derives this crate's `Write` trait from that. Implementing this crate's
+// `Write` trait here too would conflict with that blanket impl, so it's only done without `std`.
+#[cfg(not(feature = "std"))]
 impl<'a, W> Write for Writer<'a, W>
 where
     W: Write,
 {
-    /// This implementation will block until the underlying writer
-    /// has written the entire input buffer.
-    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
-        let munged: Vec<_> = self.xorcism.munge(data).collect();
-        self.writer.write_all(&munged)?;
-        Ok(data.len())
+    fn write(&mut self, data: &[u8]) -> Result<usize, Error> {
+        self.write_impl(data)
     }
 
-    fn flush(&mut self) -> io::Result<()> {
-        self.writer.flush()
+    fn flush(&mut self) -> Result<(), Error> {
+        self.flush_impl()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, W> std::io::Write for Writer<'a, W>
+where
+    W: Write,
+{
+    fn write(&mut self, data: &[u8]) -> std::io::Result<usize> {
+        self.write_impl(data).map_err(Into::into)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.flush_impl().map_err(Into::into)
     }
 }
 
@@ -166,22 +257,43 @@ where
             reader,
         }
     }
+
+    fn read_impl(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let bytes_read = self.reader.read(buf)?;
+        self.xorcism.munge_in_place(&mut buf[..bytes_read]);
+        Ok(bytes_read)
+    }
 }
 
+// Under the `std` feature, `Reader` implements `std::io::Read` directly (below), and the
+// blanket impl in `io` derives this crate's `Read` trait from that. Implementing this crate's
+// `Read` trait here too would conflict with that blanket impl, so it's only done without `std`.
+#[cfg(not(feature = "std"))]
 impl<'a, R> Read for Reader<'a, R>
 where
     R: Read,
 {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
-        let bytes_read = self.reader.read(buf)?;
-        self.xorcism.munge_in_place(&mut buf[..bytes_read]);
-        Ok(bytes_read)
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        self.read_impl(buf)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, R> std::io::Read for Reader<'a, R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.read_impl(buf).map_err(Into::into)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    // `Reader`/`Writer` also implement `std::io::{Read, Write}`; bring those in for the
+    // `read_to_end`/`write_all` extension methods used below.
+    use std::io::{Read as _, Write as _};
 
     #[test]
     fn identity() {
@@ -244,6 +356,42 @@ mod tests {
         assert_ne!(buf, data.as_bytes());
     }
 
+    #[test]
+    fn set_position_resumes_keystream() {
+        let key = "rotating key for seeking";
+        let data = "first chunk of the message, then a second chunk follows it directly.";
+
+        let mut continuous = Xorcism::new(key);
+        let continuous_out: Vec<_> = continuous.munge(data.as_bytes()).collect();
+
+        let split = data.len() / 2;
+        let mut first = Xorcism::new(key);
+        let mut first_out: Vec<_> = first.munge(&data.as_bytes()[..split]).collect();
+
+        let mut resumed = Xorcism::with_position(key, first.position());
+        first_out.extend(resumed.munge(&data.as_bytes()[split..]));
+
+        assert_eq!(continuous_out, first_out);
+    }
+
+    #[test]
+    fn set_position_wraps_modulo_key_length() {
+        let key = [1u8, 2, 3, 4];
+        let mut xs = Xorcism::new(&key);
+        xs.set_position(key.len() * 3 + 1);
+        assert_eq!(xs.position(), 1);
+    }
+
+    #[test]
+    fn set_position_tolerates_empty_key() {
+        let mut xs = Xorcism::new(&[]);
+        xs.set_position(5);
+        assert_eq!(xs.position(), 0);
+
+        let xs = Xorcism::with_position(&[], 5);
+        assert_eq!(xs.position(), 0);
+    }
+
     #[test]
     fn reader_roundtrip() {
         let data = "Mary Poppins was a kind witch. She cared for the children.";
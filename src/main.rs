@@ -62,6 +62,19 @@ mod main {
         /// Never encode output as base64
         #[structopt(long, conflicts_with = "base64")]
         no_base64: bool,
+
+        /// Wrap output in an ASCII-armor envelope
+        ///
+        /// Supersedes --base64: the output is always text-safe, checksummed, and round-trippable.
+        /// To reverse this, pipe the output back in with --dearmor.
+        #[structopt(long, conflicts_with = "dearmor")]
+        armor: bool,
+
+        /// Strip and verify an ASCII-armor envelope from the input before munging
+        ///
+        /// Reverses --armor.
+        #[structopt(long)]
+        dearmor: bool,
     }
 
     impl Opt {
@@ -112,11 +125,18 @@ mod main {
                 Err(Report::msg("key must have size > 0"))?
             }
             let reader = std::io::stdin();
-            let mut reader = std::io::BufReader::new(reader.lock());
+            let reader = std::io::BufReader::new(reader.lock());
+            let mut reader: Box<dyn std::io::Read> = if opt.dearmor {
+                Box::new(xorcism::armor::Reader::new(reader))
+            } else {
+                Box::new(reader)
+            };
 
             let writer = std::io::stdout();
             let writer = std::io::BufWriter::new(writer.lock());
-            let writer: Box<dyn std::io::Write> = if opt.base64() {
+            let writer: Box<dyn std::io::Write> = if opt.armor {
+                Box::new(xorcism::armor::Writer::new(writer))
+            } else if opt.base64() {
                 Box::new(base64::write::EncoderWriter::new(writer, base64::STANDARD))
             } else {
                 Box::new(writer)